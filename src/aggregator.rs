@@ -0,0 +1,232 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::failures::FailureReport;
+use crate::proof::{Proof, ProofShare};
+use crate::verifier::CachedVerifier;
+use lru::LruCache;
+use std::collections::BTreeMap;
+use threshold_crypto as bls;
+
+/// Maximum number of payloads with in-flight shares that are tracked at once. Sections gossip
+/// endlessly, so without a cap a flood of (possibly bogus) payloads could grow this map forever.
+const CACHE_SIZE: usize = 5;
+
+/// Aggregates `ProofShare`s for arbitrary payloads into complete `Proof`s.
+///
+/// Shares are kept per payload until a quorum of distinct, valid shares has been accepted, at
+/// which point they are combined into a `Proof` and the entry is dropped. The quorum defaults to
+/// `public_key_set.threshold() + 1` (see [`SignatureAggregator::add`]) but callers that know their
+/// section's composition should supply it explicitly via
+/// [`SignatureAggregator::add_with_quorum`]. In-flight payloads are held in a bounded LRU cache so
+/// that stale or spurious aggregations can't grow memory without limit.
+pub struct SignatureAggregator {
+    cache: LruCache<Vec<u8>, State>,
+}
+
+struct State {
+    public_key_set: bls::PublicKeySet,
+    verifier: CachedVerifier,
+    shares: BTreeMap<usize, bls::SignatureShare>,
+    failures: FailureReport,
+    quorum: usize,
+}
+
+impl SignatureAggregator {
+    /// Creates a new empty aggregator.
+    pub fn new() -> Self {
+        Self {
+            cache: LruCache::new(CACHE_SIZE),
+        }
+    }
+
+    /// Adds a `ProofShare` for `payload`.
+    ///
+    /// The quorum is the `public_key_set`'s own BLS threshold plus one.
+    /// [`SignatureAggregator::add_with_quorum`] takes the quorum explicitly instead;
+    /// [`crate::SectionKeyInfo::add_share`] calls it with
+    /// [`crate::SectionKeyInfo::quorum_count`].
+    ///
+    /// Returns `Ok(Some(proof))` once enough valid shares for `payload` have been collected to
+    /// form a complete `Proof`, `Ok(None)` if more shares are still needed, or an `Err` if `share`
+    /// is invalid or disagrees with a `public_key_set` already seen for this payload.
+    pub fn add(&mut self, payload: &[u8], share: ProofShare) -> Result<Option<Proof>, Error> {
+        let quorum = share.public_key_set.threshold() + 1;
+        self.add_with_quorum(payload, share, quorum)
+    }
+
+    /// Like [`SignatureAggregator::add`], but a quorum for `payload` is first fixed explicitly
+    /// (e.g. from [`crate::SectionKeyInfo::quorum_count`]) instead of being derived implicitly
+    /// from `share.public_key_set`.
+    pub fn add_with_quorum(
+        &mut self,
+        payload: &[u8],
+        share: ProofShare,
+        quorum: usize,
+    ) -> Result<Option<Proof>, Error> {
+        let key = payload.to_vec();
+
+        if let Some(state) = self.cache.get_mut(&key) {
+            if state.public_key_set != share.public_key_set {
+                state.failures.record(share.index);
+                return Err(Error::PublicKeySetMismatch);
+            }
+        } else {
+            let _ = self.cache.put(
+                key.clone(),
+                State {
+                    public_key_set: share.public_key_set.clone(),
+                    verifier: CachedVerifier::new(share.public_key_set.clone()),
+                    shares: BTreeMap::new(),
+                    failures: FailureReport::default(),
+                    quorum,
+                },
+            );
+        }
+
+        let state = self.cache.get_mut(&key).expect("entry just inserted");
+
+        if !state
+            .verifier
+            .verify(share.index, &share.signature_share, payload)
+        {
+            state.failures.record(share.index);
+            return Err(Error::InvalidShare);
+        }
+
+        state.failures.clear(share.index);
+        let _ = state.shares.insert(share.index, share.signature_share);
+
+        if state.shares.len() < state.quorum {
+            return Ok(None);
+        }
+
+        let signature = state
+            .public_key_set
+            .combine_signatures(state.shares.iter())
+            .map_err(|_| Error::FailedToCombine)?;
+        let proof = Proof {
+            public_key: state.public_key_set.public_key(),
+            signature,
+        };
+
+        if !proof.verify(payload) {
+            return Err(Error::FailedToCombine);
+        }
+
+        let _ = self.cache.pop(&key);
+
+        Ok(Some(proof))
+    }
+
+    /// Returns the indexes of signers whose `ProofShare` for `payload` failed verification or
+    /// disagreed on the `public_key_set`, for callers that want to surface or penalize
+    /// misbehaving elders.
+    ///
+    /// Returns an empty report if `payload` has no in-flight shares.
+    pub fn failures(&self, payload: &[u8]) -> FailureReport {
+        self.cache
+            .peek(&payload.to_vec())
+            .map(|state| state.failures.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for SignatureAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error returned by [`SignatureAggregator::add`].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The submitted `ProofShare` failed verification against the payload.
+    #[error("signature share failed verification")]
+    InvalidShare,
+    /// The submitted `ProofShare` carries a `public_key_set` that disagrees with the one already
+    /// in use for this payload.
+    #[error("public key set does not match the one already in use for this payload")]
+    PublicKeySetMismatch,
+    /// Enough shares were accepted but they could not be combined into a valid `Proof`.
+    #[error("failed to combine the accepted shares into a valid signature")]
+    FailedToCombine,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use threshold_crypto::SecretKeySet;
+
+    #[test]
+    fn aggregate() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let payload = b"hello".to_vec();
+
+        let mut aggregator = SignatureAggregator::new();
+        let mut proof = None;
+
+        for index in 0..3 {
+            let share = ProofShare::new(
+                pk_set.clone(),
+                index,
+                &sk_set.secret_key_share(index),
+                &payload,
+            );
+
+            proof = aggregator.add(&payload, share).unwrap();
+        }
+
+        let proof = proof.expect("should have produced a proof");
+        assert!(proof.verify(&payload));
+    }
+
+    #[test]
+    fn reject_invalid_share() {
+        let sk_set0 = SecretKeySet::random(2, &mut rand::thread_rng());
+        let sk_set1 = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set0 = sk_set0.public_keys();
+        let payload = b"hello".to_vec();
+
+        // Share signed with the wrong secret key set will not verify against its own claimed
+        // `public_key_set`.
+        let bad_share = ProofShare::new(pk_set0, 0, &sk_set1.secret_key_share(0), &payload);
+
+        let mut aggregator = SignatureAggregator::new();
+        assert!(matches!(
+            aggregator.add(&payload, bad_share),
+            Err(Error::InvalidShare)
+        ));
+    }
+
+    #[test]
+    fn track_and_clear_failures() {
+        let sk_set0 = SecretKeySet::random(2, &mut rand::thread_rng());
+        let sk_set1 = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set0 = sk_set0.public_keys();
+        let payload = b"hello".to_vec();
+
+        let mut aggregator = SignatureAggregator::new();
+
+        let bad_share = ProofShare::new(pk_set0.clone(), 0, &sk_set1.secret_key_share(0), &payload);
+        assert!(aggregator.add(&payload, bad_share).is_err());
+        assert_eq!(
+            aggregator
+                .failures(&payload)
+                .faulty_indexes()
+                .collect::<Vec<_>>(),
+            vec![&0]
+        );
+
+        let good_share = ProofShare::new(pk_set0, 0, &sk_set0.secret_key_share(0), &payload);
+        assert!(aggregator.add(&payload, good_share).is_ok());
+        assert!(aggregator.failures(&payload).is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Threshold decryption, mirroring the threshold signing support in [`crate::proof`].
+//!
+//! A section can encrypt a payload to its section public key with [`Ciphertext`], and its elders
+//! can then collaboratively decrypt it by each contributing a [`DecryptionShare`] without any
+//! single elder ever holding the section's full secret key.
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use threshold_crypto as bls;
+
+/// Maximum number of payloads with in-flight decryption shares that are tracked at once. Mirrors
+/// the cap used by [`crate::SignatureAggregator`].
+const CACHE_SIZE: usize = 5;
+
+/// Data encrypted to a `bls::PublicKey` (typically a section's public key).
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Ciphertext(pub bls::Ciphertext);
+
+/// Single share of a decryption of a `Ciphertext`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionShare {
+    /// BLS public key set.
+    pub public_key_set: bls::PublicKeySet,
+    /// Index of the node that created this decryption share.
+    pub index: usize,
+    /// BLS decryption share corresponding to the `index`-th public key share of the public key
+    /// set.
+    pub decryption_share: bls::DecryptionShare,
+}
+
+impl DecryptionShare {
+    /// Creates a new decryption share.
+    ///
+    /// Returns `None` if `ciphertext` fails its own internal validity check, which
+    /// `SecretKeyShare::decrypt_share` performs before producing a share.
+    pub fn new(
+        public_key_set: bls::PublicKeySet,
+        index: usize,
+        secret_key_share: &bls::SecretKeyShare,
+        ciphertext: &Ciphertext,
+    ) -> Option<Self> {
+        Some(Self {
+            public_key_set,
+            index,
+            decryption_share: secret_key_share.decrypt_share(&ciphertext.0)?,
+        })
+    }
+
+    /// Verifies this decryption share against `ciphertext`.
+    pub fn verify(&self, ciphertext: &Ciphertext) -> bool {
+        self.public_key_set
+            .public_key_share(self.index)
+            .verify_decryption_share(&self.decryption_share, &ciphertext.0)
+    }
+}
+
+impl std::fmt::Debug for DecryptionShare {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "DecryptionShare {{ public_key: {:?}, index: {}, .. }}",
+            self.public_key_set.public_key(),
+            self.index
+        )
+    }
+}
+
+/// Aggregates `DecryptionShare`s for a `Ciphertext` into the recovered plaintext.
+///
+/// Behaves like [`crate::SignatureAggregator`]: shares are kept per ciphertext until a quorum
+/// (`public_key_set.threshold() + 1` distinct, valid shares) has been accepted, at which point the
+/// plaintext is recovered and the entry is dropped. In-flight ciphertexts are held in a bounded
+/// LRU cache.
+pub struct DecryptionAggregator {
+    cache: LruCache<Vec<u8>, State>,
+}
+
+struct State {
+    public_key_set: bls::PublicKeySet,
+    shares: BTreeMap<usize, bls::DecryptionShare>,
+}
+
+impl DecryptionAggregator {
+    /// Creates a new empty aggregator.
+    pub fn new() -> Self {
+        Self {
+            cache: LruCache::new(CACHE_SIZE),
+        }
+    }
+
+    /// Adds a `DecryptionShare` for `ciphertext`.
+    ///
+    /// Returns `Ok(Some(plaintext))` once enough valid shares have been collected to recover the
+    /// plaintext, `Ok(None)` if more shares are still needed, or an `Err` if `share` is invalid or
+    /// disagrees with a `public_key_set` already seen for this ciphertext.
+    pub fn add(
+        &mut self,
+        ciphertext: &Ciphertext,
+        share: DecryptionShare,
+    ) -> Result<Option<Vec<u8>>, DecryptionError> {
+        if !share.verify(ciphertext) {
+            return Err(DecryptionError::InvalidShare);
+        }
+
+        let key = bincode::serialize(&ciphertext.0).map_err(|_| DecryptionError::InvalidShare)?;
+
+        if let Some(state) = self.cache.get(&key) {
+            if state.public_key_set != share.public_key_set {
+                return Err(DecryptionError::PublicKeySetMismatch);
+            }
+        } else {
+            let _ = self.cache.put(
+                key.clone(),
+                State {
+                    public_key_set: share.public_key_set.clone(),
+                    shares: BTreeMap::new(),
+                },
+            );
+        }
+
+        let state = self.cache.get_mut(&key).expect("entry just inserted");
+        let _ = state.shares.insert(share.index, share.decryption_share);
+
+        if state.shares.len() <= state.public_key_set.threshold() {
+            return Ok(None);
+        }
+
+        let plaintext = state
+            .public_key_set
+            .decrypt(state.shares.iter(), &ciphertext.0)
+            .map_err(|_| DecryptionError::FailedToDecrypt)?;
+
+        let _ = self.cache.pop(&key);
+
+        Ok(Some(plaintext))
+    }
+}
+
+impl Default for DecryptionAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error returned by [`DecryptionAggregator::add`].
+#[derive(Debug, thiserror::Error)]
+pub enum DecryptionError {
+    /// The submitted `DecryptionShare` failed verification against the ciphertext.
+    #[error("decryption share failed verification")]
+    InvalidShare,
+    /// The submitted `DecryptionShare` carries a `public_key_set` that disagrees with the one
+    /// already in use for this ciphertext.
+    #[error("public key set does not match the one already in use for this ciphertext")]
+    PublicKeySetMismatch,
+    /// Enough shares were accepted but they could not be combined into the plaintext.
+    #[error("failed to recover the plaintext from the accepted shares")]
+    FailedToDecrypt,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use threshold_crypto::SecretKeySet;
+
+    #[test]
+    fn aggregate() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let ciphertext = Ciphertext(pk_set.public_key().encrypt(b"hello"));
+
+        let mut aggregator = DecryptionAggregator::new();
+        let mut plaintext = None;
+
+        for index in 0..3 {
+            let share = DecryptionShare::new(
+                pk_set.clone(),
+                index,
+                &sk_set.secret_key_share(index),
+                &ciphertext,
+            )
+            .expect("ciphertext is well-formed");
+
+            plaintext = aggregator.add(&ciphertext, share).unwrap();
+        }
+
+        assert_eq!(plaintext, Some(b"hello".to_vec()));
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use std::collections::BTreeSet;
+
+/// Report of the signers whose submitted `ProofShare` failed verification, or whose
+/// `public_key_set` disagreed with the one already agreed for a payload.
+///
+/// Analogous to a `DkgFailureProofSet`, this lets a caller surface or penalize misbehaving
+/// elders instead of silently dropping their bad shares. A later valid share from the same index
+/// clears any failure previously recorded against it, since only the signer's current behaviour
+/// matters for accountability.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct FailureReport {
+    faulty_indexes: BTreeSet<usize>,
+}
+
+impl FailureReport {
+    /// Returns `true` if no failures have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.faulty_indexes.is_empty()
+    }
+
+    /// Returns the indexes recorded as having submitted a faulty `ProofShare`.
+    pub fn faulty_indexes(&self) -> impl Iterator<Item = &usize> {
+        self.faulty_indexes.iter()
+    }
+
+    pub(crate) fn record(&mut self, index: usize) {
+        let _ = self.faulty_indexes.insert(index);
+    }
+
+    pub(crate) fn clear(&mut self, index: usize) {
+        let _ = self.faulty_indexes.remove(&index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_and_clear() {
+        let mut report = FailureReport::default();
+        assert!(report.is_empty());
+
+        report.record(3);
+        assert!(!report.is_empty());
+        assert_eq!(
+            report.faulty_indexes().copied().collect::<Vec<_>>(),
+            vec![3]
+        );
+
+        report.clear(3);
+        assert!(report.is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Helpers for aggregating BLS signature shares produced by the elders of a SAFE Network section
+//! into a single `Proof` that a quorum has agreed on something.
+
+mod aggregator;
+mod decryption;
+mod failures;
+mod proof;
+mod section_key_info;
+mod signer;
+mod verifier;
+
+pub use aggregator::{Error, SignatureAggregator};
+pub use decryption::{Ciphertext, DecryptionAggregator, DecryptionError, DecryptionShare};
+pub use failures::FailureReport;
+pub use proof::{Proof, ProofShare};
+pub use section_key_info::SectionKeyInfo;
+pub use signer::{SecretKeyShareSigner, Signer};
+pub use verifier::CachedVerifier;
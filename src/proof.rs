@@ -7,6 +7,7 @@
 // specific language governing permissions and limitations relating to use of the SAFE Network
 // Software.
 
+use crate::signer::{SecretKeyShareSigner, Signer};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Formatter};
 use threshold_crypto as bls;
@@ -46,11 +47,8 @@ impl ProofShare {
         secret_key_share: &bls::SecretKeyShare,
         payload: &[u8],
     ) -> Self {
-        Self {
-            public_key_set,
-            index,
-            signature_share: secret_key_share.sign(payload),
-        }
+        SecretKeyShareSigner::new(public_key_set, index, secret_key_share.clone())
+            .sign_share(payload)
     }
 
     /// Verifies this proof share against the payload.
@@ -0,0 +1,251 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::aggregator::{Error, SignatureAggregator};
+use crate::proof::{Proof, ProofShare};
+use crate::signer::Signer;
+use std::collections::BTreeMap;
+use threshold_crypto as bls;
+
+/// Ties a section's BLS key to the elders that hold a share of it.
+///
+/// Modeled on the section's own network-info: it knows who "us" is, the `PublicKeySet` the
+/// section signs with, and which share index belongs to which elder, so callers don't need to
+/// juggle a bare `index` alongside the `PublicKeySet` or guess the signing threshold from it.
+#[derive(Clone, Debug)]
+pub struct SectionKeyInfo<Id> {
+    /// Identity of "us" - the elder this `SectionKeyInfo` was built for.
+    pub our_id: Id,
+    /// The section's BLS public key set.
+    pub public_key_set: bls::PublicKeySet,
+    indexes: BTreeMap<Id, usize>,
+}
+
+impl<Id: Ord + Clone> SectionKeyInfo<Id> {
+    /// Creates a new `SectionKeyInfo` for the elder identified by `our_id`, given the section's
+    /// `public_key_set` and the share index of every elder.
+    pub fn new(
+        our_id: Id,
+        public_key_set: bls::PublicKeySet,
+        indexes: BTreeMap<Id, usize>,
+    ) -> Self {
+        Self {
+            our_id,
+            public_key_set,
+            indexes,
+        }
+    }
+
+    /// Number of elders in the section.
+    pub fn num_nodes(&self) -> usize {
+        self.indexes.len()
+    }
+
+    /// Maximum number of faulty elders the section can tolerate while still reaching quorum.
+    ///
+    /// This is the BLS signing threshold: a `Proof` needs `num_faulty() + 1` valid shares.
+    pub fn num_faulty(&self) -> usize {
+        self.public_key_set.threshold()
+    }
+
+    /// Number of valid shares a `SignatureAggregator` needs to see before it has a quorum for
+    /// this section.
+    ///
+    /// This is `num_faulty() + 1`, i.e. the `public_key_set`'s BLS threshold plus one; the
+    /// number of elders (`num_nodes`) does not enter into it.
+    pub fn quorum_count(&self) -> usize {
+        self.num_faulty() + 1
+    }
+
+    /// Returns the share index of the elder identified by `id`.
+    pub fn index_of(&self, id: &Id) -> Option<usize> {
+        self.indexes.get(id).copied()
+    }
+
+    /// Returns our own share index.
+    pub fn our_index(&self) -> Option<usize> {
+        self.index_of(&self.our_id)
+    }
+
+    /// Builds a `ProofShare` for `payload`, signed by "us" via `signer`.
+    ///
+    /// Returns `None` if "us" isn't one of the elders this `SectionKeyInfo` knows about, or if
+    /// `signer` doesn't actually sign for our share index under our `public_key_set` (e.g. the
+    /// wrong signer, or a signer for the wrong section, was wired in).
+    pub fn sign_share_for_us(&self, signer: &impl Signer, payload: &[u8]) -> Option<ProofShare> {
+        let our_index = self.our_index()?;
+        let share = signer.sign_share(payload);
+
+        if share.index != our_index || share.public_key_set != self.public_key_set {
+            return None;
+        }
+
+        Some(share)
+    }
+
+    /// Adds `share` for `payload` to `aggregator`, using this section's own [`quorum_count`] as
+    /// the number of shares required. As `share` must carry this section's `public_key_set`, this
+    /// is the same quorum [`SignatureAggregator::add`] would derive from `share.public_key_set`.
+    ///
+    /// Returns [`Error::PublicKeySetMismatch`] if `share` doesn't carry this section's own
+    /// `public_key_set` - `SectionKeyInfo` only trusts the key it was built with, not whatever
+    /// key a submitted share happens to claim.
+    ///
+    /// [`quorum_count`]: SectionKeyInfo::quorum_count
+    pub fn add_share(
+        &self,
+        aggregator: &mut SignatureAggregator,
+        payload: &[u8],
+        share: ProofShare,
+    ) -> Result<Option<Proof>, Error> {
+        if share.public_key_set != self.public_key_set {
+            return Err(Error::PublicKeySetMismatch);
+        }
+
+        aggregator.add_with_quorum(payload, share, self.quorum_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::SecretKeyShareSigner;
+    use crate::SignatureAggregator;
+    use threshold_crypto::SecretKeySet;
+
+    #[test]
+    fn quorum_and_lookup() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+
+        let mut indexes = BTreeMap::new();
+        indexes.insert("alice", 0);
+        indexes.insert("bob", 1);
+        indexes.insert("carol", 2);
+        indexes.insert("dave", 3);
+
+        let info = SectionKeyInfo::new("alice", pk_set, indexes);
+
+        assert_eq!(info.num_nodes(), 4);
+        assert_eq!(info.num_faulty(), 2);
+        assert_eq!(info.quorum_count(), 3);
+        assert_eq!(info.index_of(&"bob"), Some(1));
+        assert_eq!(info.index_of(&"eve"), None);
+        assert_eq!(info.our_index(), Some(0));
+    }
+
+    #[test]
+    fn sign_share_for_us() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let payload = b"hello".to_vec();
+
+        let mut indexes = BTreeMap::new();
+        indexes.insert("alice", 0);
+
+        let info = SectionKeyInfo::new("alice", pk_set.clone(), indexes);
+        let signer = SecretKeyShareSigner::new(pk_set, 0, sk_set.secret_key_share(0));
+
+        let share = info
+            .sign_share_for_us(&signer, &payload)
+            .expect("alice is a known elder");
+        assert!(share.verify(&payload));
+    }
+
+    #[test]
+    fn sign_share_for_us_rejects_signer_for_wrong_index() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let payload = b"hello".to_vec();
+
+        let mut indexes = BTreeMap::new();
+        indexes.insert("alice", 0);
+        indexes.insert("bob", 1);
+
+        let info = SectionKeyInfo::new("alice", pk_set.clone(), indexes);
+        // Wrong signer wired in: signs for bob's index, not alice's.
+        let signer = SecretKeyShareSigner::new(pk_set, 1, sk_set.secret_key_share(1));
+
+        assert_eq!(info.sign_share_for_us(&signer, &payload), None);
+    }
+
+    #[test]
+    fn sign_share_for_us_rejects_signer_for_wrong_public_key_set() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let other_sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let other_pk_set = other_sk_set.public_keys();
+        let payload = b"hello".to_vec();
+
+        let mut indexes = BTreeMap::new();
+        indexes.insert("alice", 0);
+
+        let info = SectionKeyInfo::new("alice", pk_set, indexes);
+        // Wrong signer wired in: signs for the right index, but under a different section's key.
+        let signer = SecretKeyShareSigner::new(other_pk_set, 0, other_sk_set.secret_key_share(0));
+
+        assert_eq!(info.sign_share_for_us(&signer, &payload), None);
+    }
+
+    #[test]
+    fn add_share_rejects_mismatched_public_key_set() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let other_sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let other_pk_set = other_sk_set.public_keys();
+        let payload = b"hello".to_vec();
+
+        let mut indexes = BTreeMap::new();
+        indexes.insert("alice", 0);
+
+        let info = SectionKeyInfo::new("alice", pk_set, indexes);
+        let mut aggregator = SignatureAggregator::new();
+
+        // Share is internally well-formed (verifies against its own claimed key set), but that
+        // key set isn't the one `SectionKeyInfo` was built with.
+        let foreign_share =
+            ProofShare::new(other_pk_set, 0, &other_sk_set.secret_key_share(0), &payload);
+
+        assert!(matches!(
+            info.add_share(&mut aggregator, &payload, foreign_share),
+            Err(Error::PublicKeySetMismatch)
+        ));
+    }
+
+    #[test]
+    fn add_share_uses_section_quorum() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let payload = b"hello".to_vec();
+
+        let mut indexes = BTreeMap::new();
+        indexes.insert("alice", 0);
+        indexes.insert("bob", 1);
+        indexes.insert("carol", 2);
+        indexes.insert("dave", 3);
+
+        let info = SectionKeyInfo::new("alice", pk_set.clone(), indexes);
+        assert_eq!(info.quorum_count(), 3);
+
+        let mut aggregator = SignatureAggregator::new();
+        let mut proof = None;
+
+        for index in 0..info.quorum_count() {
+            let share = ProofShare::new(
+                pk_set.clone(),
+                index,
+                &sk_set.secret_key_share(index),
+                &payload,
+            );
+            proof = info.add_share(&mut aggregator, &payload, share).unwrap();
+        }
+
+        assert!(proof.expect("quorum reached").verify(&payload));
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::proof::ProofShare;
+use threshold_crypto as bls;
+
+/// Produces `ProofShare`s for a payload without exposing the signer's secret key material to the
+/// caller.
+///
+/// Implementing this instead of handing a `bls::SecretKeyShare` around lets the secret live only
+/// inside the signer - wherever it is kept, be it an in-memory share or an HSM-backed one - and be
+/// zeroized there, rather than at whatever call site last held it.
+pub trait Signer {
+    /// Signs `payload`, producing a `ProofShare`.
+    fn sign_share(&self, payload: &[u8]) -> ProofShare;
+}
+
+/// `Signer` backed by an owned BLS secret key share, the common case.
+pub struct SecretKeyShareSigner {
+    public_key_set: bls::PublicKeySet,
+    index: usize,
+    secret_key_share: bls::SecretKeyShare,
+}
+
+impl SecretKeyShareSigner {
+    /// Creates a new signer for the `index`-th share of `public_key_set`.
+    pub fn new(
+        public_key_set: bls::PublicKeySet,
+        index: usize,
+        secret_key_share: bls::SecretKeyShare,
+    ) -> Self {
+        Self {
+            public_key_set,
+            index,
+            secret_key_share,
+        }
+    }
+}
+
+impl Signer for SecretKeyShareSigner {
+    fn sign_share(&self, payload: &[u8]) -> ProofShare {
+        ProofShare {
+            public_key_set: self.public_key_set.clone(),
+            index: self.index,
+            signature_share: self.secret_key_share.sign(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use threshold_crypto::SecretKeySet;
+
+    #[test]
+    fn sign_share() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let payload = b"hello".to_vec();
+
+        let signer = SecretKeyShareSigner::new(pk_set, 0, sk_set.secret_key_share(0));
+        let share = signer.sign_share(&payload);
+
+        assert!(share.verify(&payload));
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use threshold_crypto as bls;
+
+/// Verifies BLS signature shares against a fixed `PublicKeySet`, caching the per-index
+/// `PublicKeyShare`s it derives along the way.
+///
+/// Deriving a `PublicKeyShare` from a `PublicKeySet`'s polynomial commitment is relatively
+/// expensive. When many shares are verified through the same `CachedVerifier`, this cache turns
+/// that cost from "once per share" into "once per elder". [`crate::SignatureAggregator`] keeps one
+/// `CachedVerifier` per in-flight payload, so there the cache is not shared across payloads.
+pub struct CachedVerifier {
+    public_key_set: bls::PublicKeySet,
+    key_shares: RefCell<BTreeMap<usize, bls::PublicKeyShare>>,
+}
+
+impl CachedVerifier {
+    /// Creates a new, empty verifier for `public_key_set`.
+    pub fn new(public_key_set: bls::PublicKeySet) -> Self {
+        Self {
+            public_key_set,
+            key_shares: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Verifies `signature_share`, the `index`-th share of a signature over `payload`.
+    pub fn verify(
+        &self,
+        index: usize,
+        signature_share: &bls::SignatureShare,
+        payload: &[u8],
+    ) -> bool {
+        self.key_share(index).verify(signature_share, payload)
+    }
+
+    fn key_share(&self, index: usize) -> bls::PublicKeyShare {
+        if let Some(key_share) = self.key_shares.borrow().get(&index) {
+            return *key_share;
+        }
+
+        let key_share = self.public_key_set.public_key_share(index);
+        let _ = self.key_shares.borrow_mut().insert(index, key_share);
+        key_share
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use threshold_crypto::SecretKeySet;
+
+    #[test]
+    fn verify_cached() {
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let pk_set = sk_set.public_keys();
+        let payload = b"hello".to_vec();
+        let share = sk_set.secret_key_share(0).sign(&payload);
+
+        let verifier = CachedVerifier::new(pk_set);
+
+        // Verify twice to exercise both the cache miss and the cache hit.
+        assert!(verifier.verify(0, &share, &payload));
+        assert!(verifier.verify(0, &share, &payload));
+    }
+}